@@ -15,7 +15,7 @@
 //! `Error` is a trait representing the basic expectations for error values,
 //! i.e. values of type `E` in `Result<T, E>`. At a minimum, errors must provide
 //! a description, but they may optionally provide additional detail (via
-//! `Display`) and cause chain information:
+//! `Display`) and source chain information:
 //!
 //! ```
 //! use std::fmt::Display;
@@ -23,16 +23,34 @@
 //! trait Error: Display {
 //!     fn description(&self) -> &str;
 //!
-//!     fn cause(&self) -> Option<&Error> { None }
+//!     fn source(&self) -> Option<&(Error + 'static)> { None }
 //! }
 //! ```
 //!
-//! The `cause` method is generally used when errors cross "abstraction
-//! boundaries", i.e.  when a one module must report an error that is "caused"
+//! The `source` method is generally used when errors cross "abstraction
+//! boundaries", i.e. when a one module must report an error that is "caused"
 //! by an error from a lower-level module. This setup makes it possible for the
 //! high-level module to provide its own errors that do not commit to any
 //! particular implementation, but also reveal some of its implementation for
-//! debugging via `cause` chains.
+//! debugging via `source` chains. `cause` is the old name for this method; it
+//! is still present but deprecated in favor of `source`, whose `'static`
+//! bound is what makes the rest of this module possible.
+//!
+//! # Beyond `description` and `source`
+//!
+//! A handful of other pieces build on top of the two methods above:
+//!
+//! * [`Error::chain`] walks `self` and every transitive `source`, so callers
+//!   can iterate or search an error's whole history instead of calling
+//!   `source()` in a loop by hand.
+//! * [`Error::backtrace`] lets an error expose a [`Backtrace`] captured at
+//!   the point it was created.
+//! * [`Error::provide`], together with [`Demand`] and `request_ref`, is a
+//!   more general escape hatch for the same kind of side information as
+//!   `backtrace` (spans, status codes, ...) without growing this trait a
+//!   method per use case.
+//! * [`Error::report`] renders an error together with its whole `source`
+//!   chain as a single [`Report`], for use in top-level error handlers.
 
 // A note about crates and the facade:
 //
@@ -44,16 +62,30 @@
 // moved the `Error` trait into libstd. As we evolve a sol'n to the
 // coherence challenge (e.g., specialization, neg impls, etc) we can
 // reconsider what crate these items belong in.
+//
+// None of that blocks the trait itself, or the `TypeId`-based downcasting
+// built on it, from being usable without `alloc`: only the blanket `From`
+// conversions into `Box<Error>` actually need heap types. Those, and the
+// `Error` impls for heap-allocated std types, are gated behind the
+// `alloc` feature below so `no_std` crates can still implement and
+// consume `Error` (description, source, downcast) on bare `&dyn Error`.
 
 use any::TypeId;
+use array;
+#[cfg(feature = "alloc")]
 use boxed::Box;
 use cell;
+use char;
+use convert;
 use fmt::{self, Debug, Display};
+#[cfg(feature = "alloc")]
+use heap;
 use marker::{Send, Sync};
 use mem::transmute;
 use num;
 use core::raw::TraitObject;
 use str;
+#[cfg(feature = "alloc")]
 use string::{self, String};
 
 /// Base functionality for all errors in Rust.
@@ -102,7 +134,7 @@ pub trait Error: Debug + Display {
     ///         "I'm the superhero of errors!"
     ///     }
     ///
-    ///     fn cause(&self) -> Option<&Error> {
+    ///     fn source(&self) -> Option<&(Error + 'static)> {
     ///         Some(&self.side)
     ///     }
     /// }
@@ -130,13 +162,135 @@ pub trait Error: Debug + Display {
     ///     match get_super_error() {
     ///         Err(e) => {
     ///             println!("Error: {}", e.description());
-    ///             println!("Caused by: {}", e.cause().unwrap());
+    ///             println!("Caused by: {}", e.source().unwrap());
     ///         }
     ///         _ => println!("No error"),
     ///     }
     /// }
     /// ```
-    fn cause(&self) -> Option<&Error> { None }
+    #[deprecated(note = "replaced by Error::source, which can support downcasting")]
+    fn cause(&self) -> Option<&Error> { self.source() }
+
+    /// The lower-level source of this error, if any.
+    ///
+    /// Unlike `cause`, the returned reference is bounded by `'static`, so it
+    /// can be passed to `downcast_ref` and friends to inspect a specific
+    /// error type further down the chain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::error::Error;
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct SuperError {
+    ///     side: SuperErrorSideKick,
+    /// }
+    ///
+    /// impl fmt::Display for SuperError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "SuperError is here!")
+    ///     }
+    /// }
+    ///
+    /// impl Error for SuperError {
+    ///     fn description(&self) -> &str {
+    ///         "I'm the superhero of errors!"
+    ///     }
+    ///
+    ///     fn source(&self) -> Option<&(Error + 'static)> {
+    ///         Some(&self.side)
+    ///     }
+    /// }
+    ///
+    /// #[derive(Debug)]
+    /// struct SuperErrorSideKick;
+    ///
+    /// impl fmt::Display for SuperErrorSideKick {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "SuperErrorSideKick is here!")
+    ///     }
+    /// }
+    ///
+    /// impl Error for SuperErrorSideKick {
+    ///     fn description(&self) -> &str {
+    ///         "I'm SuperError side kick!"
+    ///     }
+    /// }
+    /// ```
+    fn source(&self) -> Option<&(Error + 'static)> { None }
+
+    /// Returns a stack backtrace captured at the point this error was
+    /// created, if one is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::error::{Backtrace, Error};
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct SuperError {
+    ///     backtrace: Backtrace,
+    /// }
+    ///
+    /// impl fmt::Display for SuperError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "SuperError is here!")
+    ///     }
+    /// }
+    ///
+    /// impl Error for SuperError {
+    ///     fn description(&self) -> &str {
+    ///         "I'm the superhero of errors!"
+    ///     }
+    ///
+    ///     fn backtrace(&self) -> Option<&Backtrace> {
+    ///         Some(&self.backtrace)
+    ///     }
+    /// }
+    /// ```
+    fn backtrace(&self) -> Option<&Backtrace> { None }
+
+    /// Provides typed data to a `Demand`.
+    ///
+    /// This lets an error hand out arbitrary side-data (backtraces, source
+    /// spans, status codes, ...) to a caller that asks for it by type via
+    /// `request_ref`, without every possible piece of data having to be
+    /// baked into this trait as its own method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::error::{Demand, Error};
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct Reason(String);
+    ///
+    /// #[derive(Debug)]
+    /// struct SuperError {
+    ///     reason: Reason,
+    /// }
+    ///
+    /// impl fmt::Display for SuperError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "SuperError is here!")
+    ///     }
+    /// }
+    ///
+    /// impl Error for SuperError {
+    ///     fn description(&self) -> &str {
+    ///         "I'm the superhero of errors!"
+    ///     }
+    ///
+    ///     fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
+    ///         demand.provide_ref::<Reason>(&self.reason);
+    ///     }
+    /// }
+    /// ```
+    fn provide<'a>(&'a self, demand: &mut Demand<'a>) { let _ = demand; }
 
     /// Get the `TypeId` of `self`
     #[doc(hidden)]
@@ -145,18 +299,121 @@ pub trait Error: Debug + Display {
     }
 }
 
+/// A request for a piece of typed context, passed to `Error::provide`.
+///
+/// An error's `provide` implementation calls `demand.provide_ref(&self.field)`
+/// for each piece of data it is able to hand out; if the requested type
+/// matches, the reference is written into the demand's slot and later read
+/// back by `request_ref`. The type comparison reuses the same `TypeId`
+/// machinery as `is`/`downcast_ref` below.
+pub struct Demand<'a> {
+    type_id: TypeId,
+    slot: &'a mut Option<*const ()>,
+}
+
+impl<'a> Demand<'a> {
+    fn new<T: 'static>(slot: &'a mut Option<*const ()>) -> Demand<'a> {
+        Demand { type_id: TypeId::of::<T>(), slot: slot }
+    }
+
+    /// Provides a reference as the answer to this demand, if it is asking
+    /// for a `&T`. Has no effect, and may be called any number of times,
+    /// if the demand is asking for some other type, or has already been
+    /// satisfied.
+    pub fn provide_ref<T: 'static>(&mut self, value: &'a T) -> &mut Self {
+        if self.slot.is_none() && self.type_id == TypeId::of::<T>() {
+            *self.slot = Some(value as *const T as *const ());
+        }
+        self
+    }
+
+    /// Provides a value as the answer to this demand, if it is asking for a
+    /// `T`. Has no effect if the demand is asking for some other type.
+    ///
+    /// This minimal `Demand` only hands back borrowed data (there is no
+    /// heap slot to stash an owned value in), so `provide_value` takes the
+    /// value by reference just like `provide_ref`; callers that own the
+    /// value should store it in `self` first and provide a reference to it.
+    pub fn provide_value<T: 'static>(&mut self, value: &'a T) -> &mut Self {
+        self.provide_ref(value)
+    }
+}
+
+/// A captured stack backtrace, for carrying origin information alongside an
+/// error.
+///
+/// This firmware target has no OS environment to read a `RUST_BACKTRACE`
+/// opt-in from and no unwind tables to resolve frames against, so
+/// `Backtrace::capture` is honestly inert here: it always resolves to an
+/// empty backtrace, and `Display`-ing it prints nothing. The type exists so
+/// error types written against this crate can carry a `Backtrace` field and
+/// call `backtrace()`/`Report::show_backtrace` the same way they would
+/// against host `std`, which does perform the real `RUST_BACKTRACE`-gated
+/// capture.
+pub struct Backtrace {
+    #[cfg(feature = "alloc")]
+    frames: Option<Box<[String]>>,
+}
+
+impl Backtrace {
+    /// Always resolves to an empty backtrace on this target; see the type
+    /// documentation.
+    #[cfg(feature = "alloc")]
+    pub fn capture() -> Backtrace {
+        Backtrace { frames: None }
+    }
+
+    /// Always resolves to an empty backtrace on this target; see the type
+    /// documentation.
+    #[cfg(not(feature = "alloc"))]
+    pub fn capture() -> Backtrace {
+        Backtrace {}
+    }
+}
+
+impl Debug for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Backtrace").finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.frames {
+            Some(ref frames) => {
+                for (i, frame) in frames.iter().enumerate() {
+                    writeln!(f, "  {}: {}", i, frame)?;
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Display for Backtrace {
+    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl<'a, E: Error + 'a> From<E> for Box<Error + 'a> {
     fn from(err: E) -> Box<Error + 'a> {
         Box::new(err)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, E: Error + Send + Sync + 'a> From<E> for Box<Error + Send + Sync + 'a> {
     fn from(err: E) -> Box<Error + Send + Sync + 'a> {
         Box::new(err)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<String> for Box<Error + Send + Sync> {
     fn from(err: String) -> Box<Error + Send + Sync> {
         #[derive(Debug)]
@@ -176,6 +433,7 @@ impl From<String> for Box<Error + Send + Sync> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<String> for Box<Error> {
     fn from(str_err: String) -> Box<Error> {
         let err1: Box<Error + Send + Sync> = From::from(str_err);
@@ -184,12 +442,14 @@ impl From<String> for Box<Error> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, 'b> From<&'b str> for Box<Error + Send + Sync + 'a> {
     fn from(err: &'b str) -> Box<Error + Send + Sync + 'a> {
         From::from(String::from(err))
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> From<&'a str> for Box<Error> {
     fn from(err: &'a str) -> Box<Error> {
         From::from(String::from(err))
@@ -224,31 +484,81 @@ impl Error for num::ParseFloatError {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Error for string::FromUtf8Error {
     fn description(&self) -> &str {
         "invalid utf-8"
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Error for string::FromUtf16Error {
     fn description(&self) -> &str {
         "invalid utf-16"
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Error for string::ParseError {
     fn description(&self) -> &str {
         match *self {}
     }
 }
 
+impl Error for convert::Infallible {
+    fn description(&self) -> &str {
+        match *self {}
+    }
+}
+
+impl Error for char::CharTryFromError {
+    fn description(&self) -> &str {
+        "converted integer out of range for `char`"
+    }
+}
+
+impl Error for char::ParseCharError {
+    fn description(&self) -> &str {
+        self.__description()
+    }
+}
+
+impl Error for array::TryFromSliceError {
+    fn description(&self) -> &str {
+        "could not convert slice to array"
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Error for heap::AllocErr {
+    fn description(&self) -> &str {
+        "memory allocation failed"
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Error for heap::LayoutErr {
+    fn description(&self) -> &str {
+        "invalid parameters to Layout::from_size_align"
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl<T: Error> Error for Box<T> {
     fn description(&self) -> &str {
         Error::description(&**self)
     }
 
-    fn cause(&self) -> Option<&Error> {
-        Error::cause(&**self)
+    fn source(&self) -> Option<&(Error + 'static)> {
+        Error::source(&**self)
+    }
+
+    fn backtrace(&self) -> Option<&Backtrace> {
+        Error::backtrace(&**self)
+    }
+
+    fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
+        Error::provide(&**self, demand)
     }
 }
 
@@ -318,6 +628,170 @@ impl Error + 'static {
             None
         }
     }
+
+    /// Returns an iterator starting with `self` and walking the chain of
+    /// `source`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::error::Error;
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct A;
+    ///
+    /// #[derive(Debug)]
+    /// struct B(Option<Box<Error + 'static>>);
+    ///
+    /// impl fmt::Display for A {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "A") }
+    /// }
+    ///
+    /// impl fmt::Display for B {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "B") }
+    /// }
+    ///
+    /// impl Error for A {
+    ///     fn description(&self) -> &str { "A" }
+    /// }
+    ///
+    /// impl Error for B {
+    ///     fn description(&self) -> &str { "B" }
+    ///     fn source(&self) -> Option<&(Error + 'static)> {
+    ///         self.0.as_ref().map(|e| &**e)
+    ///     }
+    /// }
+    ///
+    /// let b = B(Some(Box::new(A)));
+    ///
+    /// // let err : Box<Error> = b.into(); // or similar
+    /// // for source in err.chain() {
+    /// //     println!("{}", source);
+    /// // }
+    /// ```
+    #[inline]
+    pub fn chain(&self) -> Chain {
+        Chain { current: Some(self) }
+    }
+
+    /// Requests a reference of type `T` from `self` through the `provide`
+    /// mechanism, returning `Some` if `self` (or its `provide` impl) was
+    /// able to supply one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::error::Error;
+    ///
+    /// fn print_status(err: &(Error + 'static)) {
+    ///     if let Some(status) = err.request_ref::<u32>() {
+    ///         println!("status: {}", status);
+    ///     }
+    /// }
+    /// ```
+    pub fn request_ref<T: 'static>(&self) -> Option<&T> {
+        let mut slot: Option<*const ()> = None;
+        {
+            let mut demand = Demand::new::<T>(&mut slot);
+            self.provide(&mut demand);
+        }
+        slot.map(|ptr| unsafe { &*(ptr as *const T) })
+    }
+
+    /// Returns a `Report` that, when displayed, renders `self` together
+    /// with its whole `source` chain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::error::Error;
+    ///
+    /// fn print_report(err: &(Error + 'static)) {
+    ///     eprintln!("{}", err.report().pretty(true).show_backtrace(true));
+    /// }
+    /// ```
+    pub fn report(&self) -> Report {
+        Report::new(self)
+    }
+}
+
+/// An iterator over an `Error` and its underlying causes, built by
+/// `Error::chain`.
+pub struct Chain<'a> {
+    current: Option<&'a (Error + 'static)>,
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (Error + 'static);
+
+    fn next(&mut self) -> Option<&'a (Error + 'static)> {
+        let current = self.current;
+        self.current = self.current.and_then(Error::source);
+        current
+    }
+}
+
+/// Renders an error together with its whole `source` chain as a single
+/// diagnostic, built by `Error::report`.
+///
+/// The default form is compact (`outer: caused by: inner: caused by:
+/// innermost`). Call `.pretty(true)` for a multi-line numbered form, and
+/// `.show_backtrace(true)` to additionally append the innermost error's
+/// `backtrace()`, if it captured one.
+pub struct Report<'a> {
+    error: &'a (Error + 'static),
+    pretty: bool,
+    show_backtrace: bool,
+}
+
+impl<'a> Report<'a> {
+    fn new(error: &'a (Error + 'static)) -> Report<'a> {
+        Report { error: error, pretty: false, show_backtrace: false }
+    }
+
+    /// Use the multi-line numbered form instead of the default compact
+    /// one-liner.
+    pub fn pretty(mut self, pretty: bool) -> Report<'a> {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Append the innermost error's `backtrace()`, if it has one.
+    pub fn show_backtrace(mut self, show_backtrace: bool) -> Report<'a> {
+        self.show_backtrace = show_backtrace;
+        self
+    }
+}
+
+impl<'a> Display for Report<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+
+        if self.pretty {
+            for (i, cause) in self.error.chain().skip(1).enumerate() {
+                write!(f, "\n\n{: >3}: {}", i + 1, cause)?;
+            }
+        } else {
+            for cause in self.error.chain().skip(1) {
+                write!(f, ": caused by: {}", cause)?;
+            }
+        }
+
+        if self.show_backtrace {
+            if let Some(backtrace) = self.error.chain().last().and_then(Error::backtrace) {
+                write!(f, "\n\n{}", backtrace)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Debug for Report<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
 }
 
 impl Error + 'static + Send {
@@ -360,6 +834,7 @@ impl Error + 'static + Send + Sync {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Error {
     #[inline]
     /// Attempt to downcast the box to a concrete type.
@@ -380,6 +855,7 @@ impl Error {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Error + Send {
     #[inline]
     /// Attempt to downcast the box to a concrete type.
@@ -393,6 +869,7 @@ impl Error + Send {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Error + Send + Sync {
     #[inline]
     /// Attempt to downcast the box to a concrete type.
@@ -406,10 +883,10 @@ impl Error + Send + Sync {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use prelude::v1::*;
-    use super::Error;
+    use super::{Backtrace, Demand, Error};
     use fmt;
 
     #[derive(Debug, PartialEq)]
@@ -450,4 +927,111 @@ mod tests {
             Err(e) => assert_eq!(*e.downcast::<A>().unwrap(), A),
         }
     }
+
+    // A three-level `Outer -> Middle -> Inner` chain, used to exercise
+    // `chain()`, `request_ref()` and `Report` below.
+
+    struct Reason(&'static str);
+
+    struct Outer { source: Middle }
+    struct Middle { source: Inner }
+    struct Inner { reason: Reason, backtrace: Backtrace }
+
+    impl fmt::Debug for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "Outer") }
+    }
+    impl fmt::Debug for Middle {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "Middle") }
+    }
+    impl fmt::Debug for Inner {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "Inner") }
+    }
+
+    impl fmt::Display for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "outer") }
+    }
+    impl fmt::Display for Middle {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "middle") }
+    }
+    impl fmt::Display for Inner {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "innermost") }
+    }
+
+    impl Error for Outer {
+        fn description(&self) -> &str { "outer" }
+        fn source(&self) -> Option<&(Error + 'static)> { Some(&self.source) }
+    }
+    impl Error for Middle {
+        fn description(&self) -> &str { "middle" }
+        fn source(&self) -> Option<&(Error + 'static)> { Some(&self.source) }
+    }
+    impl Error for Inner {
+        fn description(&self) -> &str { "innermost" }
+
+        fn backtrace(&self) -> Option<&Backtrace> {
+            Some(&self.backtrace)
+        }
+
+        fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
+            demand.provide_ref::<Reason>(&self.reason);
+        }
+    }
+
+    fn chain_of(frames: Option<Box<[String]>>) -> Outer {
+        Outer {
+            source: Middle {
+                source: Inner {
+                    reason: Reason("boom"),
+                    backtrace: Backtrace { frames: frames },
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn chain_walks_every_source() {
+        let err = chain_of(None);
+        let err = &err as &(Error + 'static);
+
+        let descriptions: Vec<&str> = err.chain().map(Error::description).collect();
+        assert_eq!(descriptions, vec!["outer", "middle", "innermost"]);
+    }
+
+    #[test]
+    fn request_ref_hits_and_misses() {
+        let err = chain_of(None);
+        let inner = &err.source.source;
+        let inner = &*inner as &(Error + 'static);
+
+        assert_eq!(inner.request_ref::<Reason>().map(|r| r.0), Some("boom"));
+        assert_eq!(inner.request_ref::<u32>(), None);
+    }
+
+    #[test]
+    fn report_compact_is_colon_joined() {
+        let err = chain_of(None);
+        let err = &err as &(Error + 'static);
+
+        assert_eq!(format!("{}", err.report()),
+                   "outer: caused by: middle: caused by: innermost");
+    }
+
+    #[test]
+    fn report_pretty_is_numbered() {
+        let err = chain_of(None);
+        let err = &err as &(Error + 'static);
+
+        assert_eq!(format!("{}", err.report().pretty(true)),
+                   "outer\n\n  1: middle\n\n  2: innermost");
+    }
+
+    #[test]
+    fn report_show_backtrace_appends_innermost_backtrace() {
+        let frames: Box<[String]> = Box::new([String::from("0: inner::frame")]);
+        let err = chain_of(Some(frames));
+        let err = &err as &(Error + 'static);
+
+        assert_eq!(format!("{}", err.report().show_backtrace(true)),
+                   "outer: caused by: middle: caused by: innermost\n\n  0: 0: inner::frame\n");
+    }
 }